@@ -0,0 +1,710 @@
+//! Turns the `#[repr(C)]` layout algorithm described in the crate root's
+//! comments into something you can actually run, instead of just reading
+//! about in a comment next to `CA`.
+//!
+//! The algorithm is exactly the one spelled out for `c_representation()`:
+//! start at offset 0, round up to each field's alignment in declaration
+//! order, then round the final offset up to the struct's alignment.
+
+/// The size and alignment of a single field, as you'd get from
+/// `size_of`/`align_of` on its type.
+///
+/// `align` must be a nonzero power of two, same as a real type's
+/// alignment always is. [`compute_repr_c`] and [`compute_repr_rust`]
+/// validate this and return [`LayoutError::InvalidAlign`] if it doesn't
+/// hold; other functions built on top of a raw `FieldSpec` (e.g.
+/// [`compute_repr_c_enum`], [`compute_union_layout`]) trust the
+/// precondition and may panic if it's violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpec {
+    pub size: usize,
+    pub align: usize,
+}
+
+/// The computed layout of a type: its overall size, its alignment, and
+/// the offset of each field (in the same order as the input slice).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub size: usize,
+    pub align: usize,
+    pub offsets: Vec<usize>,
+}
+
+/// Rounds `offset` up to the next multiple of `align`.
+///
+/// Panics if `align` is zero; callers are expected to have validated
+/// alignments first (see [`is_valid_align`]).
+fn round_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// Whether `align` is a legal alignment: a nonzero power of two, same
+/// as every real type's `align_of` value.
+fn is_valid_align(align: usize) -> bool {
+    align != 0 && align.is_power_of_two()
+}
+
+/// Checks every field's `align` against [`is_valid_align`].
+fn validate_fields(fields: &[FieldSpec]) -> Result<(), LayoutError> {
+    for field in fields {
+        if !is_valid_align(field.align) {
+            return Err(LayoutError::InvalidAlign(field.align));
+        }
+    }
+    Ok(())
+}
+
+/// `repr(align(N))` / `repr(packed(N))`, as attached to a struct, enum,
+/// or union on top of its base representation.
+///
+/// `align(N)` raises the computed alignment to `max(natural_align, N)` —
+/// it can only ever make alignment stricter, never looser, so
+/// `align(1)` is a no-op on a type whose fields already force a larger
+/// alignment. `packed(N)` does the opposite: it caps every field's
+/// *effective* alignment at `min(field_align, N)`, which can shrink the
+/// padding between fields as well as the type's own alignment.
+///
+/// `align` and `packed` can't both be set; see [`LayoutError`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReprOptions {
+    pub align: Option<usize>,
+    pub pack: Option<usize>,
+}
+
+impl ReprOptions {
+    fn validate(self) -> Result<Self, LayoutError> {
+        if self.align.is_some() && self.pack.is_some() {
+            return Err(LayoutError::ConflictingRepr);
+        }
+        for align in [self.align, self.pack].into_iter().flatten() {
+            if !is_valid_align(align) {
+                return Err(LayoutError::InvalidAlign(align));
+            }
+        }
+        Ok(self)
+    }
+
+    /// The alignment a field is actually laid out with once `pack` has
+    /// been applied.
+    fn effective_align(self, field_align: usize) -> usize {
+        match self.pack {
+            Some(pack) => field_align.min(pack),
+            None => field_align,
+        }
+    }
+
+    /// The struct/enum/union alignment once both options are applied to
+    /// the natural (max-of-fields) alignment.
+    fn effective_struct_align(self, natural_align: usize) -> usize {
+        let align = match self.pack {
+            Some(pack) => natural_align.min(pack),
+            None => natural_align,
+        };
+        match self.align {
+            Some(min_align) => align.max(min_align),
+            None => align,
+        }
+    }
+}
+
+/// Why a layout couldn't be computed: either an illegal combination of
+/// representation options, or a field/option whose alignment isn't a
+/// legal alignment to begin with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutError {
+    /// `align(N)` and `packed(N)` were both requested on the same type.
+    ConflictingRepr,
+    /// A field's `align`, or an `align`/`packed` option, wasn't a
+    /// nonzero power of two (see [`FieldSpec::align`]).
+    InvalidAlign(usize),
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutError::ConflictingRepr => {
+                write!(
+                    f,
+                    "`align` and `packed` cannot both be set on the same type"
+                )
+            }
+            LayoutError::InvalidAlign(align) => {
+                write!(f, "{align} is not a valid alignment (must be a nonzero power of two)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Packs `fields` in the order given by `order` (a permutation of
+/// `0..fields.len()`), applying `opts` to each field's alignment, and
+/// returns the offsets indexed by original field position along with
+/// the struct's total size (rounded up to the struct's alignment).
+fn pack_fields(fields: &[FieldSpec], order: &[usize], opts: ReprOptions) -> (Vec<usize>, usize) {
+    let natural_align = fields.iter().map(|f| f.align).max().unwrap_or(1);
+    let align = opts.effective_struct_align(natural_align);
+
+    let mut offset = 0;
+    let mut offsets = vec![0; fields.len()];
+    for &index in order {
+        let field = fields[index];
+        let field_align = opts.effective_align(field.align);
+        offset = round_up(offset, field_align);
+        offsets[index] = offset;
+        offset += field.size;
+    }
+
+    (offsets, round_up(offset, align))
+}
+
+/// Computes the `repr(C)` layout for a struct with the given fields, in
+/// declaration order, under the given `align`/`packed` options.
+pub fn compute_repr_c(fields: &[FieldSpec], opts: ReprOptions) -> Result<Layout, LayoutError> {
+    let opts = opts.validate()?;
+    validate_fields(fields)?;
+    let order: Vec<usize> = (0..fields.len()).collect();
+    let (offsets, size) = pack_fields(fields, &order, opts);
+    let align = opts.effective_struct_align(fields.iter().map(|f| f.align).max().unwrap_or(1));
+    Ok(Layout {
+        size,
+        align,
+        offsets,
+    })
+}
+
+/// Computes the default (`repr(Rust)`) layout for a struct with the given
+/// fields, under the given `align`/`packed` options. Declaration order
+/// doesn't matter for this representation (see the note on `DefaultA`),
+/// so the compiler is free to reorder fields to cut down on padding: it
+/// sorts by descending effective alignment, breaking ties by descending
+/// size, then packs them with the same offset/round-up rule as
+/// `repr(C)`. The returned `offsets` are mapped back to the original
+/// declaration order, so they line up with `fields` the same way
+/// `compute_repr_c`'s do.
+pub fn compute_repr_rust(fields: &[FieldSpec], opts: ReprOptions) -> Result<Layout, LayoutError> {
+    let opts = opts.validate()?;
+    validate_fields(fields)?;
+
+    let mut order: Vec<usize> = (0..fields.len()).collect();
+    order.sort_by(|&a, &b| {
+        opts.effective_align(fields[b].align)
+            .cmp(&opts.effective_align(fields[a].align))
+            .then(fields[b].size.cmp(&fields[a].size))
+    });
+
+    let (offsets, size) = pack_fields(fields, &order, opts);
+    let align = opts.effective_struct_align(fields.iter().map(|f| f.align).max().unwrap_or(1));
+    Ok(Layout {
+        size,
+        align,
+        offsets,
+    })
+}
+
+/// The number of padding bytes in `layout`, i.e. the difference between
+/// its total size and the combined size of `fields`. Comparing this
+/// between `compute_repr_c` and `compute_repr_rust` is the "Size vs.
+/// Speed" tradeoff: `repr(C)` preserves declaration order at the cost of
+/// padding, the default representation reorders fields to shrink it.
+pub fn padding_bytes(layout: &Layout, fields: &[FieldSpec]) -> usize {
+    let used: usize = fields.iter().map(|f| f.size).sum();
+    layout.size - used
+}
+
+/// The computed layout of a fieldful enum: its overall size and
+/// alignment, the size of its discriminant tag (0 when no tag is
+/// needed, whether because there's a single variant or because the
+/// discriminant was niche-optimized away), and each variant's field
+/// offsets. Offsets are relative to the start of the enum, i.e. they
+/// already account for the tag prefix (when there is one), so they show
+/// where each variant's payload lands next to the discriminant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumLayout {
+    pub size: usize,
+    pub align: usize,
+    pub tag_size: usize,
+    pub variant_offsets: Vec<Vec<usize>>,
+    pub niche_optimized: bool,
+}
+
+/// A niche: a range of bit patterns a field's type can never legally
+/// hold (e.g. the all-zero pattern for a non-null pointer, or the
+/// unused byte values of a `bool`). The compiler can store an enum's
+/// discriminant in a niche instead of allocating a separate tag field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NicheInfo {
+    pub offset: usize,
+    pub available_values: u128,
+}
+
+/// A field as seen by [`compute_enum_layout`]'s niche-optimization pass:
+/// the field itself, plus the niche it offers (if any) for the compiler
+/// to fold a discriminant into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NicheField {
+    pub field: FieldSpec,
+    pub niche: Option<NicheInfo>,
+}
+
+/// Tries the niche-filling optimization the compiler applies to enums
+/// like `Option<&T>`: when exactly one of two variants is empty and the
+/// other holds a single field with a niche big enough to cover the
+/// discriminant (`available_values >= variant_count - 1`), the enum can
+/// reuse that niche instead of adding a tag, so it ends up with the
+/// same size and alignment as the payload field alone.
+fn try_niche_layout(variants: &[Vec<NicheField>]) -> Option<EnumLayout> {
+    if variants.len() != 2 {
+        return None;
+    }
+    let (empty, payload) = match (variants[0].is_empty(), variants[1].is_empty()) {
+        (true, false) => (0, 1),
+        (false, true) => (1, 0),
+        _ => return None,
+    };
+    let [field] = variants[payload].as_slice() else {
+        return None;
+    };
+    let niche = field.niche?;
+    if niche.available_values < variants.len() as u128 - 1 {
+        return None;
+    }
+
+    let layout = compute_repr_c(&[field.field], ReprOptions::default())
+        .expect("default options never conflict");
+
+    let mut variant_offsets = vec![Vec::new(); variants.len()];
+    variant_offsets[payload] = layout.offsets;
+    variant_offsets[empty] = Vec::new();
+
+    Some(EnumLayout {
+        size: layout.size,
+        align: layout.align,
+        tag_size: 0,
+        variant_offsets,
+        niche_optimized: true,
+    })
+}
+
+/// The size of the smallest unsigned integer tag that can distinguish
+/// `variant_count` variants — *not* the largest that fits, the smallest
+/// that's sufficient.
+fn tag_size_for(variant_count: usize) -> usize {
+    let variant_count = variant_count as u64;
+    match variant_count {
+        0 | 1 => 0,
+        n if n <= 1 << 8 => 1,
+        n if n <= 1 << 16 => 2,
+        n if n <= 1 << 32 => 4,
+        _ => 8,
+    }
+}
+
+/// Computes the default (`repr(Rust)`) layout of a fieldful enum, given
+/// each variant's fields (with niche info, for the niche-filling pass)
+/// in declaration order.
+///
+/// First tries the niche-filling optimization (see [`try_niche_layout`]);
+/// if that doesn't apply, falls back to an ordinary tagged layout:
+/// chooses the smallest tag type that can index all the variants, treats
+/// it as a prefix field shared by every variant, and lays each variant
+/// out as a `repr(C)`-style struct of `{ tag, ...fields }`. The enum's
+/// alignment is the max over the tag and every variant; its size is the
+/// largest variant (tag included) rounded up to that alignment.
+///
+/// A single-variant enum needs no tag at all (see the note on
+/// `DefaultEnumSingle`): it's laid out as if it were just that variant's
+/// fields, with `tag_size` left at 0.
+pub fn compute_enum_layout(variants: &[Vec<NicheField>]) -> EnumLayout {
+    if variants.is_empty() {
+        return EnumLayout {
+            size: 0,
+            align: 1,
+            tag_size: 0,
+            variant_offsets: vec![],
+            niche_optimized: false,
+        };
+    }
+
+    if variants.len() == 1 {
+        let fields: Vec<FieldSpec> = variants[0].iter().map(|nf| nf.field).collect();
+        let layout = compute_repr_c(&fields, ReprOptions::default())
+            .expect("default options never conflict");
+        return EnumLayout {
+            size: layout.size,
+            align: layout.align,
+            tag_size: 0,
+            variant_offsets: vec![layout.offsets],
+            niche_optimized: false,
+        };
+    }
+
+    if let Some(layout) = try_niche_layout(variants) {
+        return layout;
+    }
+
+    let tag_size = tag_size_for(variants.len());
+    let tag = FieldSpec {
+        size: tag_size,
+        align: tag_size,
+    };
+
+    let mut align = tag.align;
+    let mut size = 0;
+    let mut variant_offsets = Vec::with_capacity(variants.len());
+
+    for fields in variants {
+        let mut prefixed = Vec::with_capacity(fields.len() + 1);
+        prefixed.push(tag);
+        prefixed.extend(fields.iter().map(|nf| nf.field));
+
+        let layout = compute_repr_c(&prefixed, ReprOptions::default())
+            .expect("default options never conflict");
+
+        align = align.max(layout.align);
+        size = size.max(layout.size);
+        variant_offsets.push(layout.offsets[1..].to_vec());
+    }
+
+    EnumLayout {
+        size: round_up(size, align),
+        align,
+        tag_size,
+        variant_offsets,
+        niche_optimized: false,
+    }
+}
+
+/// Computes the layout of a union with the given fields, following the
+/// overlapping-fields model: every field starts at offset 0, the
+/// union's alignment is the max over all field alignments, and its size
+/// is the max field size rounded up to that alignment (which may
+/// legally exceed the largest field). The returned `offsets` are all
+/// zero, kept only for API uniformity with the struct/enum functions.
+pub fn compute_union_layout(fields: &[FieldSpec]) -> Layout {
+    let align = fields.iter().map(|f| f.align).max().unwrap_or(1);
+    let size = round_up(fields.iter().map(|f| f.size).max().unwrap_or(0), align);
+    Layout {
+        size,
+        align,
+        offsets: vec![0; fields.len()],
+    }
+}
+
+/// Computes the layout of a fieldful `repr(C, primitive)` enum (RFC
+/// 2195), by lowering it to the equivalent `repr(C)` struct of
+/// `{ tag, payload }` that the commented-out sketch in the crate root
+/// gestures at:
+///
+/// ```text
+/// #[repr(C)]
+/// struct CEnumRepr {
+///     tag: MyEnumDiscriminant,
+///     payload: MyEnumFields,
+/// }
+/// ```
+///
+/// The payload is a [`compute_union_layout`] of every variant, each
+/// collapsed to the single `FieldSpec` its own `repr(C)` layout
+/// produces. That payload is then combined with `tag` using the
+/// ordinary `repr(C)` struct rule. The returned `Layout`'s two offsets
+/// are `[tag_offset, payload_offset]`.
+pub fn compute_repr_c_enum(tag: FieldSpec, variants: &[Vec<FieldSpec>]) -> Layout {
+    let variant_fields: Vec<FieldSpec> = variants
+        .iter()
+        .map(|fields| {
+            let layout = compute_repr_c(fields, ReprOptions::default())
+                .expect("default options never conflict");
+            FieldSpec {
+                size: layout.size,
+                align: layout.align,
+            }
+        })
+        .collect();
+
+    let payload_layout = compute_union_layout(&variant_fields);
+    let payload = FieldSpec {
+        size: payload_layout.size,
+        align: payload_layout.align,
+    };
+
+    compute_repr_c(&[tag, payload], ReprOptions::default()).expect("default options never conflict")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ca_layout_matches_the_worked_example() {
+        // #[repr(C)] struct CA { a: i32, b: i64 } -> offsets [0, 8], size 16, align 8
+        let fields = [
+            FieldSpec { size: 4, align: 4 },
+            FieldSpec { size: 8, align: 8 },
+        ];
+        let layout = compute_repr_c(&fields, ReprOptions::default()).unwrap();
+        assert_eq!(layout.offsets, vec![0, 8]);
+        assert_eq!(layout.size, 16);
+        assert_eq!(layout.align, 8);
+    }
+
+    #[test]
+    fn fields_pack_without_padding_when_already_aligned() {
+        let fields = [
+            FieldSpec { size: 1, align: 1 },
+            FieldSpec { size: 1, align: 1 },
+        ];
+        let layout = compute_repr_c(&fields, ReprOptions::default()).unwrap();
+        assert_eq!(layout.offsets, vec![0, 1]);
+        assert_eq!(layout.size, 2);
+        assert_eq!(layout.align, 1);
+    }
+
+    #[test]
+    fn empty_struct_has_size_zero_and_align_one() {
+        let layout = compute_repr_c(&[], ReprOptions::default()).unwrap();
+        assert_eq!(layout.size, 0);
+        assert_eq!(layout.align, 1);
+        assert!(layout.offsets.is_empty());
+    }
+
+    #[test]
+    fn default_repr_reorders_to_put_the_wide_field_first() {
+        // struct { a: i32, b: i64 } reordered as [b, a] -> b at 0, a at 8, size 16
+        let fields = [
+            FieldSpec { size: 4, align: 4 },
+            FieldSpec { size: 8, align: 8 },
+        ];
+        let layout = compute_repr_rust(&fields, ReprOptions::default()).unwrap();
+        assert_eq!(layout.offsets, vec![8, 0]);
+        assert_eq!(layout.size, 16);
+        assert_eq!(layout.align, 8);
+    }
+
+    #[test]
+    fn default_repr_saves_padding_over_repr_c() {
+        // declaration order [i8, i32, i8] costs repr(C) 6 bytes of padding;
+        // reordering to [i32, i8, i8] only costs the trailing 2 to round
+        // the struct up to its 4-byte alignment.
+        let fields = [
+            FieldSpec { size: 1, align: 1 },
+            FieldSpec { size: 4, align: 4 },
+            FieldSpec { size: 1, align: 1 },
+        ];
+        let c_layout = compute_repr_c(&fields, ReprOptions::default()).unwrap();
+        let rust_layout = compute_repr_rust(&fields, ReprOptions::default()).unwrap();
+        assert_eq!(padding_bytes(&c_layout, &fields), 6);
+        assert_eq!(padding_bytes(&rust_layout, &fields), 2);
+    }
+
+    #[test]
+    fn align_raises_but_never_lowers_alignment() {
+        let fields = [FieldSpec { size: 4, align: 4 }];
+
+        // align(1) is a no-op: natural alignment (4) already exceeds it.
+        let opts = ReprOptions {
+            align: Some(1),
+            pack: None,
+        };
+        let layout = compute_repr_c(&fields, opts).unwrap();
+        assert_eq!(layout.align, 4);
+        assert_eq!(layout.size, 4);
+
+        // align(16) raises it.
+        let opts = ReprOptions {
+            align: Some(16),
+            pack: None,
+        };
+        let layout = compute_repr_c(&fields, opts).unwrap();
+        assert_eq!(layout.align, 16);
+        assert_eq!(layout.size, 16);
+    }
+
+    #[test]
+    fn packed_caps_field_alignment_and_removes_padding() {
+        // #[repr(C, packed(1))] struct CA { a: i32, b: i64 } packs solid:
+        // offsets [0, 4], size 12, align 1.
+        let fields = [
+            FieldSpec { size: 4, align: 4 },
+            FieldSpec { size: 8, align: 8 },
+        ];
+        let opts = ReprOptions {
+            align: None,
+            pack: Some(1),
+        };
+        let layout = compute_repr_c(&fields, opts).unwrap();
+        assert_eq!(layout.offsets, vec![0, 4]);
+        assert_eq!(layout.size, 12);
+        assert_eq!(layout.align, 1);
+    }
+
+    #[test]
+    fn align_and_packed_together_is_an_error() {
+        let fields = [FieldSpec { size: 4, align: 4 }];
+        let opts = ReprOptions {
+            align: Some(8),
+            pack: Some(1),
+        };
+        assert_eq!(
+            compute_repr_c(&fields, opts),
+            Err(LayoutError::ConflictingRepr)
+        );
+    }
+
+    /// A plain field with no niche, for enum-layout tests that don't
+    /// care about niche optimization.
+    fn no_niche(size: usize, align: usize) -> NicheField {
+        NicheField {
+            field: FieldSpec { size, align },
+            niche: None,
+        }
+    }
+
+    #[test]
+    fn default_enum_matches_the_commented_observation() {
+        // enum { A(i32), B(i32), C(i32), D(i32) } -> size 8 (max size + tag), align 4
+        let variants = vec![
+            vec![no_niche(4, 4)],
+            vec![no_niche(4, 4)],
+            vec![no_niche(4, 4)],
+            vec![no_niche(4, 4)],
+        ];
+        let layout = compute_enum_layout(&variants);
+        assert_eq!(layout.tag_size, 1);
+        assert_eq!(layout.size, 8);
+        assert_eq!(layout.align, 4);
+        assert!(!layout.niche_optimized);
+        assert_eq!(
+            layout.variant_offsets,
+            vec![vec![4], vec![4], vec![4], vec![4]]
+        );
+    }
+
+    #[test]
+    fn single_variant_enum_has_no_tag() {
+        // enum { A(i32) } -> size 4, no tag, per DefaultEnumSingle
+        let variants = vec![vec![no_niche(4, 4)]];
+        let layout = compute_enum_layout(&variants);
+        assert_eq!(layout.tag_size, 0);
+        assert_eq!(layout.size, 4);
+        assert_eq!(layout.align, 4);
+        assert_eq!(layout.variant_offsets, vec![vec![0]]);
+    }
+
+    #[test]
+    fn option_reference_is_niche_optimized_to_pointer_size() {
+        // Option<&T> -> None is empty, Some holds a non-null pointer whose
+        // all-zero bit pattern is the one forbidden value, which is
+        // exactly enough niche to store the 2-variant discriminant.
+        let pointer_field = NicheField {
+            field: FieldSpec { size: 8, align: 8 },
+            niche: Some(NicheInfo {
+                offset: 0,
+                available_values: 1,
+            }),
+        };
+        let variants = vec![vec![], vec![pointer_field]];
+        let layout = compute_enum_layout(&variants);
+        assert!(layout.niche_optimized);
+        assert_eq!(layout.tag_size, 0);
+        assert_eq!(layout.size, 8);
+        assert_eq!(layout.align, 8);
+        assert_eq!(layout.variant_offsets, vec![vec![], vec![0]]);
+    }
+
+    #[test]
+    fn insufficient_niche_falls_back_to_a_tagged_layout() {
+        // A field with zero available niche values can't encode even the
+        // one other variant that needs to be distinguished, so the niche
+        // optimization must fall back to an ordinary tag.
+        let field = NicheField {
+            field: FieldSpec { size: 1, align: 1 },
+            niche: Some(NicheInfo {
+                offset: 0,
+                available_values: 0,
+            }),
+        };
+        let variants = vec![vec![], vec![field]];
+        let layout = compute_enum_layout(&variants);
+        assert!(!layout.niche_optimized);
+        assert_eq!(layout.tag_size, 1);
+    }
+
+    #[test]
+    fn tag_size_grows_with_variant_count() {
+        assert_eq!(tag_size_for(1), 0);
+        assert_eq!(tag_size_for(2), 1);
+        assert_eq!(tag_size_for(256), 1);
+        assert_eq!(tag_size_for(257), 2);
+        assert_eq!(tag_size_for(65536), 2);
+        assert_eq!(tag_size_for(65537), 4);
+    }
+
+    #[test]
+    fn repr_c_enum_lowers_to_tag_plus_union_of_variants() {
+        // #[repr(C, u32)] enum { A(i32), B(i64) }: tag(4,4), payload union
+        // of [i32(4,4), i64(8,8)] -> size 8, align 8. Combined struct
+        // { tag, payload } under repr(C): tag at 0, payload at 8 (to
+        // satisfy the union's 8-byte alignment), total size 16, align 8.
+        let tag = FieldSpec { size: 4, align: 4 };
+        let variants = vec![
+            vec![FieldSpec { size: 4, align: 4 }],
+            vec![FieldSpec { size: 8, align: 8 }],
+        ];
+        let layout = compute_repr_c_enum(tag, &variants);
+        assert_eq!(layout.offsets, vec![0, 8]);
+        assert_eq!(layout.size, 16);
+        assert_eq!(layout.align, 8);
+    }
+
+    #[test]
+    fn repr_c_enum_payload_union_is_exactly_the_widest_variant() {
+        // Every variant the same shape as CEnum's i32 payload: tag(4,4)
+        // and payload union of a single i32 -> payload (4,4), combined
+        // struct has no padding, size 8.
+        let tag = FieldSpec { size: 4, align: 4 };
+        let variants = vec![
+            vec![FieldSpec { size: 4, align: 4 }],
+            vec![FieldSpec { size: 4, align: 4 }],
+        ];
+        let layout = compute_repr_c_enum(tag, &variants);
+        assert_eq!(layout.offsets, vec![0, 4]);
+        assert_eq!(layout.size, 8);
+        assert_eq!(layout.align, 4);
+    }
+
+    #[test]
+    fn union_fields_all_start_at_offset_zero() {
+        let fields = [
+            FieldSpec { size: 4, align: 4 },
+            FieldSpec { size: 8, align: 8 },
+            FieldSpec { size: 1, align: 1 },
+        ];
+        let layout = compute_union_layout(&fields);
+        assert_eq!(layout.offsets, vec![0, 0, 0]);
+        assert_eq!(layout.align, 8);
+        assert_eq!(layout.size, 8);
+    }
+
+    #[test]
+    fn union_size_rounds_up_to_its_alignment() {
+        // A 5-byte field next to a 4-byte-aligned one can't just take the
+        // max size (5) -- the union's own size must still be a multiple
+        // of its alignment.
+        let fields = [
+            FieldSpec { size: 5, align: 1 },
+            FieldSpec { size: 2, align: 4 },
+        ];
+        let layout = compute_union_layout(&fields);
+        assert_eq!(layout.align, 4);
+        assert_eq!(layout.size, 8);
+    }
+
+    #[test]
+    fn empty_union_has_size_zero_and_align_one() {
+        let layout = compute_union_layout(&[]);
+        assert_eq!(layout.size, 0);
+        assert_eq!(layout.align, 1);
+        assert!(layout.offsets.is_empty());
+    }
+}
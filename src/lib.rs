@@ -12,6 +12,8 @@
 //!
 //!
 
+pub mod layout;
+
 // 0. primitive data layout
 
 struct A;
@@ -92,6 +94,17 @@ fn default_representation() {
 
     println!("{:?}", std::mem::size_of::<DefaultEnumSingle>()); //this is 4 = no tag
     println!("{:?}", std::mem::align_of::<DefaultEnumSingle>());
+
+    // Same numbers as DefaultA above, but computed from `layout::compute_repr_rust`
+    // instead of read off a running compiler's `size_of`/`align_of`.
+    let default_a_fields = [
+        layout::FieldSpec { size: 4, align: 4 }, // a: i32
+        layout::FieldSpec { size: 8, align: 8 }, // b: i64
+    ];
+    let default_a_layout =
+        layout::compute_repr_rust(&default_a_fields, layout::ReprOptions::default())
+            .expect("default options never conflict");
+    println!("{:?}", default_a_layout);
 }
 
 //2. C
@@ -129,6 +142,16 @@ fn c_representation() {
 
     println!("{:?}", std::mem::size_of::<CEnumSingle>());
     println!("{:?}", std::mem::align_of::<CEnumSingle>());
+
+    // Same numbers as CA above, but computed from `layout::compute_repr_c`
+    // instead of read off a running compiler's `size_of`/`align_of`.
+    let ca_fields = [
+        layout::FieldSpec { size: 4, align: 4 }, // a: i32
+        layout::FieldSpec { size: 8, align: 8 }, // b: i64
+    ];
+    let ca_layout = layout::compute_repr_c(&ca_fields, layout::ReprOptions::default())
+        .expect("default options never conflict");
+    println!("{:?}", ca_layout);
 }
 
 // // ... this struct.